@@ -1,48 +1,301 @@
 //! The `Client` is used to access all API methods.
+use std::time::{Duration, Instant};
+
 use oauth2;
 use hyper;
 use hyper::client::HttpConnector;
+use hyper::header::{Authorization, Bearer, ContentLength, ContentType};
+use hyper_tls::HttpsConnector;
 use futures::{Future, Stream};
 use tokio_core::reactor::Core;
+use serde::de::DeserializeOwned;
+use serde_json;
+use url::Url;
+use base64;
+use rand::Rng;
+
+use models::{ApiErrorBody, Board, DataEnvelope, PageEnvelope, Pin};
+use pkce::PkceChallenge;
+use scopes::{ScopeKind, Scopes};
+
+/// A fully parsed OAuth2 token response: the bearer token itself, when (if ever) it
+/// expires, and the refresh token/scope needed to renew it.
+///
+/// `expires_in` on the raw token response is relative to the moment the response was
+/// received, so it is converted into an absolute `Instant` up front — that way
+/// `is_expired` stays correct no matter how long the token sits unused.
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub access_token: String,
+    pub token_type: String,
+    pub refresh_token: Option<String>,
+    /// The scopes actually granted, parsed out of the token endpoint's (possibly
+    /// space- or comma-joined) `scope` field so it can be compared against a `Scopes`
+    /// built with `Scopes::new()` instead of matched against raw strings.
+    pub scope: Scopes,
+    expires_at: Option<Instant>,
+}
+
+impl Token {
+    /// Whether this token is known to have expired.  A token whose response never
+    /// included `expires_in` is treated as never expiring.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.map_or(false, |at| Instant::now() >= at)
+    }
+}
+
+impl From<oauth2::Token> for Token {
+    fn from(token: oauth2::Token) -> Self {
+        Token {
+            access_token: token.access_token,
+            token_type: token.token_type,
+            expires_at: token.expires_in.map(|secs| Instant::now() + Duration::from_secs(secs as u64)),
+            refresh_token: token.refresh_token,
+            scope: token.scopes.join(" ").parse().unwrap_or_default(),
+        }
+    }
+}
 
 /// Base API request string
 const API_BASE: &str = "https://api.pinterest.com/v1/";
 
-/// Defines what permissions the token should grant.  By default, all are false.
-pub struct Scope {
-    /// Use GET method on a user’s Pins, boards.
-    read_public: bool,
-    /// Use PATCH, POST and DELETE methods on a user’s Pins and boards.
-    write_public: bool,
-    /// Use GET method on a user’s follows and followers (on boards, users and interests).
-    read_relationships: bool,
-    /// Use PATCH, POST and DELETE methods on a user’s follows and followers (on boards, users and interests).
-    write_relationships: bool,
+/// Fluent builder for a Pinterest authorization URL.  Unlike `TokenBuilder`, which needs
+/// a full `Config` (client secret, token URL, ...) to later exchange a code, this only
+/// needs what's required to send a user to Pinterest's consent screen — handy for
+/// generating "Login with Pinterest" links up front.
+pub struct AuthUrlBuilder<'a> {
+    client_id: &'a str,
+    authorize_url: &'a str,
+    redirect_url: &'a str,
+    scopes: Scopes,
+    state: Option<String>,
+    pkce: PkceChallenge,
+}
+
+impl<'a> AuthUrlBuilder<'a> {
+    /// Create a builder for an app registered with `client_id`, whose consent screen
+    /// lives at `authorize_url` and which redirects back to `redirect_url`.
+    ///
+    /// A fresh PKCE `code_verifier`/`code_challenge` pair is generated here and the `S256`
+    /// challenge is appended by `build`, per RFC 7636; call `verifier` (or `into_pkce`, to
+    /// hand the whole pair to `TokenBuilder::pkce`) so the matching `code_verifier` can be
+    /// sent back when the returned `code` is exchanged.
+    pub fn new(client_id: &'a str, authorize_url: &'a str, redirect_url: &'a str) -> Self {
+        AuthUrlBuilder {
+            client_id: client_id,
+            authorize_url: authorize_url,
+            redirect_url: redirect_url,
+            scopes: Scopes::new(),
+            state: None,
+            pkce: PkceChallenge::new(),
+        }
+    }
+
+    /// The PKCE `code_verifier` generated for this authorization attempt; send it back as
+    /// `code_verifier` when exchanging the returned `code` for a token.
+    pub fn verifier(&self) -> &str {
+        self.pkce.verifier()
+    }
+
+    /// Consume this builder, returning the `PkceChallenge` it generated so it can be handed
+    /// to `TokenBuilder::pkce` and reused for `exchange_code`.
+    pub fn into_pkce(self) -> PkceChallenge {
+        self.pkce
+    }
+
+    /// Request `scopes` on the resulting authorization URL.
+    pub fn scopes(mut self, scopes: Scopes) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Attach a CSRF `state` token, echoed back by Pinterest on the redirect so the
+    /// callback can be matched to the request that started it.
+    pub fn state<S: Into<String>>(mut self, state: S) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+
+    /// Build the full authorization URL a user should be redirected to.
+    pub fn build(&self) -> Url {
+        let mut url = Url::parse(self.authorize_url).expect("authorize_url must be a valid URL");
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs.append_pair("client_id", self.client_id);
+            pairs.append_pair("scope", &self.scopes.to_string());
+            pairs.append_pair("response_type", "code");
+            pairs.append_pair("redirect_uri", self.redirect_url);
+            pairs.append_pair("code_challenge", self.pkce.challenge());
+            pairs.append_pair("code_challenge_method", self.pkce.method());
+            if let Some(ref state) = self.state {
+                pairs.append_pair("state", state);
+            }
+        }
+        url
+    }
+}
+
+/// A standard OAuth2 token-endpoint error code (RFC 6749 §5.2), plus whatever code the
+/// server sent that we don't specifically recognize.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OAuth2ErrorCode {
+    InvalidRequest,
+    InvalidClient,
+    InvalidGrant,
+    UnauthorizedClient,
+    UnsupportedGrantType,
+    InvalidScope,
+    Other(String),
 }
 
-impl Default for Scope {
-    fn default() -> Scope {
-        Scope {
-            read_public: false,
-            write_public: false,
-            read_relationships: false,
-            write_relationships: false,
+impl OAuth2ErrorCode {
+    fn parse(code: &str) -> Self {
+        match code {
+            "invalid_request" => OAuth2ErrorCode::InvalidRequest,
+            "invalid_client" => OAuth2ErrorCode::InvalidClient,
+            "invalid_grant" => OAuth2ErrorCode::InvalidGrant,
+            "unauthorized_client" => OAuth2ErrorCode::UnauthorizedClient,
+            "unsupported_grant_type" => OAuth2ErrorCode::UnsupportedGrantType,
+            "invalid_scope" => OAuth2ErrorCode::InvalidScope,
+            other => OAuth2ErrorCode::Other(other.to_string()),
         }
     }
 }
 
+/// The raw `{ "error": "...", "error_description": "..." }` body a token endpoint sends
+/// back on failure, per RFC 6749 §5.2.
+#[derive(Debug, Deserialize)]
+struct OAuth2ErrorBody {
+    error: String,
+    error_description: Option<String>,
+}
+
+/// The raw success body a token endpoint sends back, per RFC 6749 §5.1. `scope` is
+/// space-delimited on the wire, not an array.
+#[derive(Debug, Deserialize)]
+struct TokenResponseBody {
+    access_token: String,
+    token_type: String,
+    expires_in: Option<u64>,
+    refresh_token: Option<String>,
+    scope: Option<String>,
+}
+
+impl From<TokenResponseBody> for Token {
+    fn from(body: TokenResponseBody) -> Self {
+        Token {
+            access_token: body.access_token,
+            token_type: body.token_type,
+            expires_at: body.expires_in.map(|secs| Instant::now() + Duration::from_secs(secs)),
+            refresh_token: body.refresh_token,
+            scope: body.scope.map(|scope| scope.parse().unwrap_or_default()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Encode a list of key/value pairs as `application/x-www-form-urlencoded` body bytes.
+fn form_encode(pairs: &[(String, String)]) -> Vec<u8> {
+    url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs)
+        .finish()
+        .into_bytes()
+}
+
+/// POST `form` to `uri` and return the raw response status and body, without attaching any
+/// authentication — used for the OAuth2 token endpoint, which authenticates via
+/// `client_id`/`client_secret` in the body rather than a bearer token.
+fn post_form(
+    hyper: &hyper::Client<HttpsConnector<HttpConnector>>,
+    core: &mut Core,
+    uri: hyper::Uri,
+    form: &[(String, String)],
+) -> Result<(hyper::StatusCode, Vec<u8>), PinterestError> {
+    let bytes = form_encode(form);
+    let mut req = hyper::Request::new(hyper::Method::Post, uri);
+    req.headers_mut().set(ContentType::form_url_encoded());
+    req.headers_mut().set(ContentLength(bytes.len() as u64));
+    req.set_body(bytes);
+
+    let work = hyper.request(req).and_then(|res| {
+        let status = res.status();
+        res.body().concat2().map(move |chunk| (status, chunk.to_vec()))
+    });
+    Ok(core.run(work)?)
+}
+
+/// Build an `https`-capable `hyper::Client` driven by `core`. Every Pinterest endpoint
+/// (API and OAuth2 token) is `https`, and a plain `HttpConnector` rejects that scheme
+/// outright, so every client in this crate goes through this instead of `hyper::Client::new`.
+fn https_client(core: &Core) -> hyper::Client<HttpsConnector<HttpConnector>> {
+    let https = HttpsConnector::new(4, &core.handle()).expect("TLS initialization failed");
+    hyper::Client::configure().connector(https).build(&core.handle())
+}
+
+/// Parse a non-2xx token-endpoint response into a `PinterestError`, preferring the standard
+/// `{ "error": ... }` body (RFC 6749 §5.2) and falling back to the raw response text.
+fn token_error(status: hyper::StatusCode, body: &[u8]) -> PinterestError {
+    match serde_json::from_slice::<OAuth2ErrorBody>(body) {
+        Ok(err) => PinterestError::OAuth2(OAuth2ErrorCode::parse(&err.error), err.error_description),
+        Err(_) => PinterestError::Api { status: status, message: String::from_utf8_lossy(body).into_owned() },
+    }
+}
+
 /// Defines Pinterest error types
 #[derive(Debug)]
 pub enum PinterestError {
+    /// The OAuth2 token endpoint rejected the request with a standard `error` code and an
+    /// optional human-readable `error_description`.
+    OAuth2(OAuth2ErrorCode, Option<String>),
+    /// Refreshing the token through the `oauth2` crate failed; its error carries no
+    /// standard OAuth2 error code.
     Token(oauth2::TokenError),
+    /// The token used to build this `Client` was not granted the scope a call requires.
+    InsufficientScope(&'static str),
+    /// The underlying HTTP request failed.
+    Http(hyper::Error),
+    /// The Pinterest API responded with a non-2xx status and an error body.
+    Api { status: hyper::StatusCode, message: String },
+    /// The API returned a body that could not be deserialized into the expected type.
+    Deserialize(serde_json::Error),
+    /// `Client::refresh_token` was called but the client has no refresh token, or was
+    /// never given the OAuth2 configuration needed to use one.
+    MissingRefreshToken,
 }
 
 impl From<oauth2::TokenError> for PinterestError {
+    /// `oauth2::TokenError` doesn't surface the raw response body it was built from, only a
+    /// prose `Display`, so there's no reliable way to recover a standard OAuth2 `error` code
+    /// from it here. `TokenBuilder::exchange_code` therefore talks to the token endpoint
+    /// directly instead of going through `oauth2::Config::exchange_code`, so it can parse the
+    /// real body and produce `PinterestError::OAuth2` itself; this impl only wraps whatever
+    /// opaque error the `oauth2` crate's own token-refresh path still produces.
     fn from(err: oauth2::TokenError) -> PinterestError {
         PinterestError::Token(err)
     }
 }
 
+impl From<hyper::Error> for PinterestError {
+    fn from(err: hyper::Error) -> PinterestError {
+        PinterestError::Http(err)
+    }
+}
+
+impl From<serde_json::Error> for PinterestError {
+    fn from(err: serde_json::Error) -> PinterestError {
+        PinterestError::Deserialize(err)
+    }
+}
+
+/// The three ways the Pinterest pin-creation endpoint accepts an image.
+pub enum PinImage<'a> {
+    /// A publicly reachable URL the API will fetch itself.
+    Url(&'a str),
+    /// Raw image bytes, sent base64-encoded.
+    Base64(&'a [u8]),
+    /// Raw image bytes uploaded directly as a `multipart/form-data` file.
+    File { bytes: &'a [u8], filename: &'a str },
+}
+
 /// Defines general OAuth2 configuration with Pinterest specific options
 pub struct Config<'a> {
     client_id: &'a str,
@@ -50,36 +303,313 @@ pub struct Config<'a> {
     authorize_url: &'a str,
     token_url: &'a str,
     redirect_url: &'a str,
-    scope: Scope,
+    scope: Scopes,
 }
 
 /// Handles API methods
-pub struct Client { 
-    token: Option<oauth2::Token>,
-    hyper: hyper::Client<HttpConnector>, 
+pub struct Client {
+    token: Option<Token>,
+    scope: Scopes,
+    /// Present when this `Client` was built via `TokenBuilder::client`; required by
+    /// `refresh_token` to know where and as whom to ask for a new access token.
+    config: Option<oauth2::Config>,
+    hyper: hyper::Client<HttpsConnector<HttpConnector>>,
     core: Core,
 }
 
 impl Default for Client {
     fn default() -> Self {
-        let mut core = Core::new().unwrap();
+        let core = Core::new().unwrap();
         Client {
             token: None,
-            hyper: hyper::Client::new(&core.handle()),
+            scope: Scopes::new(),
+            config: None,
+            hyper: https_client(&core),
             core: core,
         }
     }
 }
 
 impl Client {
-    pub fn new(token: oauth2::Token) -> Self {
-        Client { token: Some(token), .. Client::default() }
+    /// Build a `Client` from a token exchanged via `TokenBuilder::exchange_code` and the
+    /// `Scopes` it was requested with.  The scopes are required here (rather than read back
+    /// off the token) so calls can be rejected locally instead of round-tripping to the API.
+    ///
+    /// A `Client` built this way has no way to refresh its token once it expires; use
+    /// `TokenBuilder::client` instead if the token carries a `refresh_token`.
+    pub fn new(token: Token, scope: Scopes) -> Self {
+        Client {
+            token: Some(token),
+            scope: scope,
+            ..Client::default()
+        }
+    }
+
+    /// Exchange the stored refresh token for a new access token, replacing the current one.
+    ///
+    /// Per RFC 6749 §6, the refresh response may omit `refresh_token` entirely, meaning the
+    /// client should keep using the one it already has — so the prior value is carried over
+    /// whenever the new response doesn't supply its own.
+    pub fn refresh_token(&mut self) -> Result<(), PinterestError> {
+        let refresh_token = self.token
+            .as_ref()
+            .and_then(|token| token.refresh_token.clone())
+            .ok_or(PinterestError::MissingRefreshToken)?;
+        let config = self.config.as_ref().ok_or(PinterestError::MissingRefreshToken)?;
+        let token = config.exchange_refresh_token(&refresh_token)?;
+        let mut new_token = Token::from(token);
+        if new_token.refresh_token.is_none() {
+            new_token.refresh_token = Some(refresh_token);
+        }
+        self.token = Some(new_token);
+        Ok(())
+    }
+
+    /// Refresh the stored token if it has expired and this client holds an OAuth2
+    /// configuration; an expired token with no `refresh_token` then fails with
+    /// `PinterestError::MissingRefreshToken`.
+    fn ensure_fresh_token(&mut self) -> Result<(), PinterestError> {
+        let expired = self.token.as_ref().map_or(false, |token| token.is_expired());
+        if expired && self.config.is_some() {
+            self.refresh_token()?;
+        }
+        Ok(())
+    }
+
+    /// Return a board by id.
+    pub fn get_board(&mut self, board: &str, fields: Option<&[&str]>) -> Result<Board, PinterestError> {
+        self.require_scope(ScopeKind::ReadPublic)?;
+        let uri = Self::endpoint_uri(&format!("boards/{}/", board), fields);
+        let body = self.send(hyper::Method::Get, uri, None)?;
+        Self::parse_single(&body)
+    }
+
+    /// List the pins belonging to the authenticated user.
+    pub fn list_pins(&mut self, fields: Option<&[&str]>) -> Result<Vec<Pin>, PinterestError> {
+        self.require_scope(ScopeKind::ReadPublic)?;
+        let uri = Self::endpoint_uri("me/pins/", fields);
+        let body = self.send(hyper::Method::Get, uri, None)?;
+        Self::parse_list(&body)
+    }
+
+    /// Create a pin on `board` linking to `link` with an image fetched from `image_url`.
+    pub fn create_pin(
+        &mut self,
+        board: &str,
+        note: &str,
+        link: Option<&str>,
+        image_url: &str,
+        fields: Option<&[&str]>,
+    ) -> Result<Pin, PinterestError> {
+        self.require_scope(ScopeKind::WritePublic)?;
+        let mut form = vec![
+            ("board".to_string(), board.to_string()),
+            ("note".to_string(), note.to_string()),
+            ("image_url".to_string(), image_url.to_string()),
+        ];
+        if let Some(link) = link {
+            form.push(("link".to_string(), link.to_string()));
+        }
+        let uri = Self::endpoint_uri("pins/", fields);
+        let body = self.send(hyper::Method::Post, uri, Some((form_encode(&form), "application/x-www-form-urlencoded".to_string())))?;
+        Self::parse_single(&body)
+    }
+
+    /// Create a pin on `board`, supplying its image via whichever of the three modes
+    /// `PinImage` describes.  This is the general form of `create_pin`, which only covers
+    /// the `image_url` case.
+    pub fn post_pin<'a>(
+        &mut self,
+        board: &str,
+        note: &str,
+        link: Option<&str>,
+        image: PinImage<'a>,
+        fields: Option<&[&str]>,
+    ) -> Result<Pin, PinterestError> {
+        self.require_scope(ScopeKind::WritePublic)?;
+        let uri = Self::endpoint_uri("pins/", fields);
+
+        let body = match image {
+            PinImage::Url(image_url) => {
+                let mut form = vec![
+                    ("board".to_string(), board.to_string()),
+                    ("note".to_string(), note.to_string()),
+                    ("image_url".to_string(), image_url.to_string()),
+                ];
+                if let Some(link) = link {
+                    form.push(("link".to_string(), link.to_string()));
+                }
+                self.send(hyper::Method::Post, uri, Some((form_encode(&form), "application/x-www-form-urlencoded".to_string())))?
+            }
+            PinImage::Base64(bytes) => {
+                let mut form = vec![
+                    ("board".to_string(), board.to_string()),
+                    ("note".to_string(), note.to_string()),
+                    ("image_base64".to_string(), base64::encode(bytes)),
+                ];
+                if let Some(link) = link {
+                    form.push(("link".to_string(), link.to_string()));
+                }
+                self.send(hyper::Method::Post, uri, Some((form_encode(&form), "application/x-www-form-urlencoded".to_string())))?
+            }
+            PinImage::File { bytes, filename } => {
+                let mut form_fields = vec![("board", board), ("note", note)];
+                if let Some(link) = link {
+                    form_fields.push(("link", link));
+                }
+                let (content_type, multipart_body) = Self::multipart_encode(&form_fields, "image", filename, bytes);
+                self.send(hyper::Method::Post, uri, Some((multipart_body, content_type)))?
+            }
+        };
+
+        Self::parse_single(&body)
+    }
+
+    /// Update the note, board or link of an existing pin.
+    pub fn update_pin(
+        &mut self,
+        pin: &str,
+        note: Option<&str>,
+        board: Option<&str>,
+        link: Option<&str>,
+        fields: Option<&[&str]>,
+    ) -> Result<Pin, PinterestError> {
+        self.require_scope(ScopeKind::WritePublic)?;
+        let mut form = Vec::new();
+        if let Some(note) = note {
+            form.push(("note".to_string(), note.to_string()));
+        }
+        if let Some(board) = board {
+            form.push(("board".to_string(), board.to_string()));
+        }
+        if let Some(link) = link {
+            form.push(("link".to_string(), link.to_string()));
+        }
+        let uri = Self::endpoint_uri(&format!("pins/{}/", pin), fields);
+        let body = self.send(hyper::Method::Patch, uri, Some((form_encode(&form), "application/x-www-form-urlencoded".to_string())))?;
+        Self::parse_single(&body)
+    }
+
+    /// Delete a pin by id.
+    pub fn delete_pin(&mut self, pin: &str) -> Result<(), PinterestError> {
+        self.require_scope(ScopeKind::WritePublic)?;
+        let uri = Self::endpoint_uri(&format!("pins/{}/", pin), None);
+        self.send(hyper::Method::Delete, uri, None)?;
+        Ok(())
+    }
+
+    /// Fail with `PinterestError::InsufficientScope` unless `kind` was granted.
+    fn require_scope(&self, kind: ScopeKind) -> Result<(), PinterestError> {
+        if self.scope.contains(kind) {
+            Ok(())
+        } else {
+            Err(PinterestError::InsufficientScope(kind.as_str()))
+        }
+    }
+
+    /// Build the URI for an endpoint under `API_BASE`, attaching a `fields` query parameter
+    /// when the caller asked for extra attributes.
+    fn endpoint_uri(path: &str, fields: Option<&[&str]>) -> hyper::Uri {
+        let mut url = Url::parse(API_BASE)
+            .and_then(|base| base.join(path))
+            .expect("endpoint path is always a valid relative URL");
+        if let Some(fields) = fields {
+            url.query_pairs_mut().append_pair("fields", &fields.join(","));
+        }
+        url.as_str().parse().expect("endpoint url is always a valid URI")
+    }
+
+    /// Encode `fields` and a single file upload as a `multipart/form-data` body, returning
+    /// the `Content-Type` header value (carrying the boundary) alongside the body bytes.
+    fn multipart_encode(fields: &[(&str, &str)], file_field: &str, filename: &str, bytes: &[u8]) -> (String, Vec<u8>) {
+        let boundary = Self::multipart_boundary();
+        let mut body = Vec::new();
+        for (name, value) in fields {
+            body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+            body.extend_from_slice(format!("Content-Disposition: form-data; name=\"{}\"\r\n\r\n", name).as_bytes());
+            body.extend_from_slice(value.as_bytes());
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+        body.extend_from_slice(
+            format!("Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n", file_field, filename).as_bytes(),
+        );
+        body.extend_from_slice(b"Content-Type: application/octet-stream\r\n\r\n");
+        body.extend_from_slice(bytes);
+        body.extend_from_slice(b"\r\n");
+        body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+
+        (format!("multipart/form-data; boundary={}", boundary), body)
+    }
+
+    /// Generate a random multipart boundary that won't collide with anything an image or
+    /// form field could contain.
+    fn multipart_boundary() -> String {
+        let mut rng = rand::OsRng::new().expect("OS CSPRNG is unavailable");
+        let suffix: String = (0..32).map(|_| std::char::from_digit(rng.gen_range(0, 16), 16).unwrap()).collect();
+        format!("pinterestrs-boundary-{}", suffix)
+    }
+
+    /// Send a bearer-authenticated request against the stored `hyper::Client`, driven through
+    /// the stored `Core`, and return the raw response body.  `body` is the request body
+    /// bytes together with the `Content-Type` to send them as.
+    fn send(&mut self, method: hyper::Method, uri: hyper::Uri, body: Option<(Vec<u8>, String)>) -> Result<Vec<u8>, PinterestError> {
+        self.ensure_fresh_token()?;
+
+        let token = self.token
+            .as_ref()
+            .expect("Client requires a token to make API calls")
+            .access_token
+            .clone();
+
+        let mut req = hyper::Request::new(method, uri);
+        req.headers_mut().set(Authorization(Bearer { token: token }));
+        if let Some((bytes, content_type)) = body {
+            req.headers_mut().set(ContentType(content_type.parse().expect("content type is always valid")));
+            req.headers_mut().set(ContentLength(bytes.len() as u64));
+            req.set_body(bytes);
+        }
+
+        let work = self.hyper.request(req).and_then(|res| {
+            let status = res.status();
+            res.body().concat2().map(move |chunk| (status, chunk.to_vec()))
+        });
+        let (status, body) = self.core.run(work)?;
+        if !status.is_success() {
+            let message = serde_json::from_slice::<ApiErrorBody>(&body)
+                .map(|err| err.message)
+                .unwrap_or_else(|_| String::from_utf8_lossy(&body).into_owned());
+            return Err(PinterestError::Api { status: status, message: message });
+        }
+        Ok(body)
+    }
+
+    fn parse_single<T: DeserializeOwned>(body: &[u8]) -> Result<T, PinterestError> {
+        let envelope: DataEnvelope<T> = serde_json::from_slice(body)?;
+        Ok(envelope.data)
+    }
+
+    fn parse_list<T: DeserializeOwned>(body: &[u8]) -> Result<Vec<T>, PinterestError> {
+        let envelope: PageEnvelope<T> = serde_json::from_slice(body)?;
+        Ok(envelope.data)
     }
 }
 
 /// Handles authentication with OAuth flow
-pub struct TokenBuilder { 
+pub struct TokenBuilder {
     config: oauth2::Config,
+    /// The PKCE verifier generated for this single auth attempt; kept around so
+    /// `exchange_code` can send it back as `code_verifier`.
+    pkce: PkceChallenge,
+    /// `exchange_code` talks to `token_url` directly with `hyper` rather than going through
+    /// `oauth2::Config::exchange_code`, so it can inspect the raw response body and recover
+    /// a standard OAuth2 error code on failure (see `token_error`).
+    token_url: String,
+    client_id: String,
+    client_secret: String,
+    redirect_url: String,
+    hyper: hyper::Client<HttpsConnector<HttpConnector>>,
+    core: Core,
 }
 
 impl TokenBuilder {
@@ -89,8 +619,12 @@ impl TokenBuilder {
     /// find a way to listen to the callback URL and receive an access token.  Please
     /// see [the oauth2 documentation](https://github.com/ramosbugs/oauth2-rs/blob/master/examples/github.rs#L82)
     /// for a way to do this.
+    ///
+    /// A fresh PKCE `code_verifier`/`code_challenge` pair is generated here and the
+    /// `S256` challenge is appended to the authorization URL, per RFC 7636.
     pub fn new(config: Config) -> Self {
-        // build the oauth2 config structure
+        // build the oauth2 config structure, used for the authorization URL and, once handed
+        // to a `Client` by `TokenBuilder::client`, for refreshing the token
         let mut oauth_config = oauth2::Config::new(
             config.client_id,
             config.client_secret,
@@ -99,30 +633,74 @@ impl TokenBuilder {
         );
 
         // add scope to config
-        if config.scope.read_public {
-            oauth_config = oauth_config.add_scope("read_public");
-        }
-        if config.scope.read_relationships {
-            oauth_config = oauth_config.add_scope("read_relationships");
-        }
-        if config.scope.write_public {
-            oauth_config = oauth_config.add_scope("write_public");
-        }
-        if config.scope.write_relationships {
-            oauth_config = oauth_config.add_scope("write_relationships");
+        for kind in config.scope.iter() {
+            oauth_config = oauth_config.add_scope(kind.as_str());
         }
 
         // set redirect URL
         oauth_config = oauth_config.set_redirect_url(config.redirect_url);
 
-        // return our `TokenBuilder`
-        TokenBuilder { config: oauth_config }
+        // generate a PKCE challenge for this attempt and attach it to the authorize URL
+        let pkce = PkceChallenge::new();
+        oauth_config = oauth_config
+            .add_extra_param("code_challenge", pkce.challenge().to_string())
+            .add_extra_param("code_challenge_method", pkce.method().to_string());
+
+        let core = Core::new().unwrap();
+        TokenBuilder {
+            config: oauth_config,
+            pkce: pkce,
+            token_url: config.token_url.to_string(),
+            client_id: config.client_id.to_string(),
+            client_secret: config.client_secret.to_string(),
+            redirect_url: config.redirect_url.to_string(),
+            hyper: https_client(&core),
+            core: core,
+        }
     }
 
-    /// Exchange for an access token which can then be used to create a `Client`.
-    pub fn exchange_code(&self, code: &str) -> Result<oauth2::Token, PinterestError> {
-        let token = self.config.exchange_code(code)?;
-        Ok(token)
+    /// Override the PKCE challenge generated in `new` with one already sent out on an
+    /// authorization URL, e.g. via `AuthUrlBuilder::into_pkce` — so the `code_verifier` sent
+    /// by `exchange_code` matches the `code_challenge` the user's browser was redirected with.
+    pub fn pkce(mut self, pkce: PkceChallenge) -> Self {
+        self.pkce = pkce;
+        self
+    }
+
+    /// Exchange `code` for an access token which can then be used to create a `Client`.
+    ///
+    /// This posts directly to the token endpoint rather than going through
+    /// `oauth2::Config::exchange_code`, so that a non-2xx response's body can be parsed for a
+    /// standard OAuth2 `error` code (RFC 6749 §5.2) instead of being lost behind an opaque
+    /// `oauth2::TokenError`.
+    pub fn exchange_code(&mut self, code: &str) -> Result<Token, PinterestError> {
+        let form = vec![
+            ("grant_type".to_string(), "authorization_code".to_string()),
+            ("code".to_string(), code.to_string()),
+            ("redirect_uri".to_string(), self.redirect_url.clone()),
+            ("client_id".to_string(), self.client_id.clone()),
+            ("client_secret".to_string(), self.client_secret.clone()),
+            ("code_verifier".to_string(), self.pkce.verifier().to_string()),
+        ];
+        let uri: hyper::Uri = self.token_url.parse().expect("token_url is always a valid URI");
+        let (status, body) = post_form(&self.hyper, &mut self.core, uri, &form)?;
+        if !status.is_success() {
+            return Err(token_error(status, &body));
+        }
+        let response: TokenResponseBody = serde_json::from_slice(&body)?;
+        Ok(Token::from(response))
+    }
+
+    /// Consume this `TokenBuilder` into a `Client` authenticated with `token` and granted
+    /// `scope`.  Retaining the OAuth2 configuration here (rather than in `Client::new`) is
+    /// what lets the resulting `Client` call `refresh_token` once `token` expires.
+    pub fn client(self, token: Token, scope: Scopes) -> Client {
+        Client {
+            token: Some(token),
+            scope: scope,
+            config: Some(self.config),
+            ..Client::default()
+        }
     }
 }
 
@@ -132,14 +710,17 @@ mod tests {
 
     #[test]
     fn create_authentication_url() {
-        let config = Config { 
-            client_id: "myclientid", 
-            client_secret: "myclientsecret",  
+        let config = Config {
+            client_id: "myclientid",
+            client_secret: "myclientsecret",
             authorize_url: "https://example.com/authorize",
             token_url: "https://example.com/token",
             redirect_url: "https://mysite.com:8000",
-            scope: Scope { read_public: true, read_relationships: true, ..Scope::default() }
+            scope: Scopes::new().read_public().read_relationships(),
         };
-        assert_eq!(TokenBuilder::new(config).config.authorize_url().as_str(), "https://example.com/authorize?client_id=myclientid&scope=read_public+read_relationships&response_type=code&redirect_uri=https%3A%2F%2Fmysite.com%3A8000");
+        let url = TokenBuilder::new(config).config.authorize_url().as_str().to_string();
+        assert!(url.starts_with("https://example.com/authorize?client_id=myclientid&scope=read_public+read_relationships&response_type=code&redirect_uri=https%3A%2F%2Fmysite.com%3A8000"));
+        assert!(url.contains("code_challenge_method=S256"));
+        assert!(url.contains("code_challenge="));
     }
 }
@@ -5,7 +5,18 @@
 extern crate oauth2;
 extern crate url;
 extern crate hyper;
+extern crate hyper_tls;
 extern crate futures;
 extern crate tokio_core;
+extern crate serde;
+extern crate serde_json;
+#[macro_use]
+extern crate serde_derive;
+extern crate rand;
+extern crate sha2;
+extern crate base64;
 
 pub mod client;
+pub mod models;
+pub mod pkce;
+pub mod scopes;
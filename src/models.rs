@@ -0,0 +1,69 @@
+//! Typed representations of the objects returned by the Pinterest API.
+//!
+//! These mirror the JSON the API hands back for pins, boards and users.  Most
+//! fields are optional because the API only includes them when they were
+//! requested via the `fields` query parameter (see `Client::get_board` and
+//! friends).
+
+use serde_json::Value;
+
+/// A Pinterest user, as embedded in a `Pin` or `Board`, or returned directly
+/// from `me` endpoints.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: String,
+    pub username: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub image: Option<Value>,
+}
+
+/// A Pinterest board.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Board {
+    pub id: String,
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub description: Option<String>,
+    pub creator: Option<User>,
+    pub counts: Option<Value>,
+    pub image: Option<Value>,
+}
+
+/// A Pinterest pin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pin {
+    pub id: String,
+    pub link: Option<String>,
+    pub url: Option<String>,
+    pub creator: Option<User>,
+    pub board: Option<Board>,
+    pub note: Option<String>,
+    pub color: Option<String>,
+    pub counts: Option<Value>,
+    pub media: Option<Value>,
+    pub attribution: Option<Value>,
+    pub origin_link: Option<String>,
+    pub image: Option<Value>,
+}
+
+/// Wraps the single-object `{ "data": ... }` envelope the API returns for
+/// `GET`/`POST`/`PATCH` calls against a single pin or board.
+#[derive(Debug, Deserialize)]
+pub(crate) struct DataEnvelope<T> {
+    pub data: T,
+}
+
+/// Wraps the paginated `{ "data": [...], "page": { ... } }` envelope the API
+/// returns for list endpoints such as `me/pins/`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct PageEnvelope<T> {
+    pub data: Vec<T>,
+}
+
+/// The JSON error body the API returns alongside a non-2xx status, e.g.
+/// `{ "message": "...", "type": "...", "code": 1 }`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ApiErrorBody {
+    pub message: String,
+}
@@ -0,0 +1,68 @@
+//! Proof Key for Code Exchange (RFC 7636) for OAuth2 clients that can't keep a
+//! `client_secret` safe, e.g. native or single-page apps.
+
+use base64;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+/// Length, in characters, of generated `code_verifier`s.  RFC 7636 allows 43–128;
+/// 64 gives comfortable entropy margin without approaching the upper bound.
+const VERIFIER_LEN: usize = 64;
+
+/// RFC 7636 `unreserved` characters a `code_verifier` may be built from.
+const UNRESERVED: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-._~";
+
+/// A `code_verifier`/`code_challenge` pair for a single authorization attempt.
+///
+/// The verifier is generated from a CSPRNG and must be kept only for the lifetime of
+/// the attempt that created it: `TokenBuilder` holds the one it generates in `new` (or the
+/// one passed to `TokenBuilder::pkce`) and sends it back as `code_verifier` in `exchange_code`.
+pub struct PkceChallenge {
+    verifier: String,
+    challenge: String,
+    method: &'static str,
+}
+
+impl PkceChallenge {
+    /// Generate a verifier and its `S256` challenge: `BASE64URL(SHA256(code_verifier))`.
+    pub fn new() -> Self {
+        let verifier = Self::generate_verifier();
+        let challenge = Self::s256(&verifier);
+        PkceChallenge { verifier: verifier, challenge: challenge, method: "S256" }
+    }
+
+    /// Generate a verifier using the `plain` method, where the challenge is the
+    /// verifier itself.  Only use this against a server that doesn't support `S256`.
+    pub fn new_plain() -> Self {
+        let verifier = Self::generate_verifier();
+        let challenge = verifier.clone();
+        PkceChallenge { verifier: verifier, challenge: challenge, method: "plain" }
+    }
+
+    /// The `code_verifier` to send with `exchange_code`.
+    pub fn verifier(&self) -> &str {
+        &self.verifier
+    }
+
+    /// The `code_challenge` to send when building the authorization URL.
+    pub fn challenge(&self) -> &str {
+        &self.challenge
+    }
+
+    /// The `code_challenge_method` to send alongside `challenge`.
+    pub fn method(&self) -> &str {
+        self.method
+    }
+
+    fn generate_verifier() -> String {
+        let mut rng = rand::OsRng::new().expect("OS CSPRNG is unavailable");
+        (0..VERIFIER_LEN)
+            .map(|_| UNRESERVED[rng.gen_range(0, UNRESERVED.len())] as char)
+            .collect()
+    }
+
+    fn s256(verifier: &str) -> String {
+        let digest = Sha256::digest(verifier.as_bytes());
+        base64::encode_config(&digest, base64::URL_SAFE_NO_PAD)
+    }
+}
@@ -0,0 +1,115 @@
+//! The permissions a Pinterest OAuth2 token can request or have been granted.
+
+use std::collections::BTreeSet;
+use std::collections::btree_set;
+use std::fmt;
+use std::str::FromStr;
+
+/// A single Pinterest OAuth2 scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ScopeKind {
+    /// Use GET method on a user’s Pins, boards.
+    ReadPublic,
+    /// Use GET method on a user’s follows and followers (on boards, users and interests).
+    ReadRelationships,
+    /// Use PATCH, POST and DELETE methods on a user’s Pins and boards.
+    WritePublic,
+    /// Use PATCH, POST and DELETE methods on a user’s follows and followers (on boards, users and interests).
+    WriteRelationships,
+}
+
+impl ScopeKind {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match *self {
+            ScopeKind::ReadPublic => "read_public",
+            ScopeKind::ReadRelationships => "read_relationships",
+            ScopeKind::WritePublic => "write_public",
+            ScopeKind::WriteRelationships => "write_relationships",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<ScopeKind> {
+        match s {
+            "read_public" => Some(ScopeKind::ReadPublic),
+            "read_relationships" => Some(ScopeKind::ReadRelationships),
+            "write_public" => Some(ScopeKind::WritePublic),
+            "write_relationships" => Some(ScopeKind::WriteRelationships),
+            _ => None,
+        }
+    }
+}
+
+/// A set of Pinterest OAuth2 scopes, either requested when building an authorization
+/// URL or granted back on a token.  Replaces the old all-or-nothing `Scope` struct of
+/// four booleans with a builder that can be constructed, compared and round-tripped
+/// through the space- or comma-joined string form the API itself uses.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Scopes(BTreeSet<ScopeKind>);
+
+impl Scopes {
+    /// An empty set of scopes.
+    pub fn new() -> Self {
+        Scopes(BTreeSet::new())
+    }
+
+    /// Request GET access to a user's Pins and boards.
+    pub fn read_public(mut self) -> Self {
+        self.0.insert(ScopeKind::ReadPublic);
+        self
+    }
+
+    /// Request PATCH/POST/DELETE access to a user's Pins and boards.
+    pub fn write_public(mut self) -> Self {
+        self.0.insert(ScopeKind::WritePublic);
+        self
+    }
+
+    /// Request GET access to a user's follows and followers.
+    pub fn read_relationships(mut self) -> Self {
+        self.0.insert(ScopeKind::ReadRelationships);
+        self
+    }
+
+    /// Request PATCH/POST/DELETE access to a user's follows and followers.
+    pub fn write_relationships(mut self) -> Self {
+        self.0.insert(ScopeKind::WriteRelationships);
+        self
+    }
+
+    /// Whether `kind` is present in this set.
+    pub fn contains(&self, kind: ScopeKind) -> bool {
+        self.0.contains(&kind)
+    }
+
+    /// Iterate the scopes in this set, in a stable order.
+    pub fn iter(&self) -> btree_set::Iter<ScopeKind> {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for Scopes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let joined = self.0.iter().map(ScopeKind::as_str).collect::<Vec<_>>().join(" ");
+        write!(f, "{}", joined)
+    }
+}
+
+impl FromStr for Scopes {
+    type Err = ();
+
+    /// Parse a space- or comma-joined scope string, as granted back on a token or
+    /// persisted by an application.  Unrecognized tokens are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut scopes = Scopes::new();
+        for part in s.split(|c| c == ' ' || c == ',') {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+            if let Some(kind) = ScopeKind::from_str(part) {
+                scopes.0.insert(kind);
+            }
+        }
+        Ok(scopes)
+    }
+}